@@ -27,6 +27,7 @@ type DownloadItem = {
   downloaded: number;
   total?: number;
   speed: number;
+  eta_seconds?: number | null;
   status: string;
   path?: string;
 };
@@ -87,10 +88,9 @@ export default function App() {
   const [autoLoginTried, setAutoLoginTried] = useState(false);
   const [maxConcurrent, setMaxConcurrent] = useState(() => {
     const raw = localStorage.getItem("maxConcurrent");
-    const parsed = raw ? Number(raw) : 2;
-    return Number.isFinite(parsed) && parsed > 0 ? parsed : 2;
+    const parsed = raw ? Number(raw) : 3;
+    return Number.isFinite(parsed) && parsed > 0 ? parsed : 3;
   });
-  const [queue, setQueue] = useState<{ archiveId: string; name: string; fileIndex?: number }[]>([]);
   const [folders, setFolders] = useState<Folder[]>([]);
   const [archives, setArchives] = useState<Archive[]>([]);
   const [currentFolderId, setCurrentFolderId] = useState<string | null>(null);
@@ -136,6 +136,14 @@ export default function App() {
 
   useEffect(() => {
     const unlisten = listen<DownloadItem>("download-progress", (event) => {
+      if (event.payload.status === "cancelled") {
+        setDownloads((prev) => {
+          const next = { ...prev };
+          delete next[event.payload.id];
+          return next;
+        });
+        return;
+      }
       setDownloads((prev) => ({
         ...prev,
         [event.payload.id]: {
@@ -144,8 +152,18 @@ export default function App() {
         }
       }));
     });
+    const unlistenBatch = listen<DownloadItem[]>("download-progress-batch", (event) => {
+      setDownloads((prev) => {
+        const next = { ...prev };
+        for (const item of event.payload) {
+          next[item.id] = { ...prev[item.id], ...item };
+        }
+        return next;
+      });
+    });
     return () => {
       unlisten.then((f) => f());
+      unlistenBatch.then((f) => f());
     };
   }, []);
 
@@ -461,6 +479,20 @@ export default function App() {
     };
   }, [downloadMenu]);
 
+  const promptUnlock = async () => {
+    const unlockPassword = window.prompt("The master key is locked. Enter your password to unlock it:");
+    if (!unlockPassword) {
+      addLog("warn", "Master key is locked; unlock it before downloading");
+      return;
+    }
+    try {
+      await invoke<string>("unlock_master_key", { password: unlockPassword });
+      addLog("info", "Master key unlocked");
+    } catch (err) {
+      addLog("error", `Master key unlock failed: ${String(err)}`);
+    }
+  };
+
   const connect = async (silent = false) => {
     if (!serverUrl || !username || !password) return;
     setConnecting(true);
@@ -468,11 +500,19 @@ export default function App() {
     setLoadError("");
     try {
       addLog("info", "Login start");
-      const key = await invoke<string>("login", { input: { server_url: serverUrl, username, password } });
+      const session = await invoke<{ master_key: string; key_status: "available" | "locked" | "absent" }>("login", {
+        input: { server_url: serverUrl, username, password }
+      });
       localStorage.setItem("serverUrl", serverUrl);
       localStorage.setItem("username", username);
-      localStorage.setItem("password", password);
-      localStorage.setItem("masterKey", key);
+      localStorage.removeItem("password");
+      localStorage.removeItem("masterKey");
+      invoke("save_credentials").catch((err) => addLog("warn", `Keyring save failed: ${String(err)}`));
+      if (session.key_status === "locked") {
+        await promptUnlock();
+      } else if (!session.master_key) {
+        addLog("warn", "Server did not provide a master key; set one manually before downloading");
+      }
       if (downloadPath) localStorage.setItem("downloadPath", downloadPath);
       setConnected(true);
       await loadRemote(null);
@@ -497,9 +537,33 @@ export default function App() {
   useEffect(() => {
     if (autoLoginTried) return;
     setAutoLoginTried(true);
-    if (serverUrl && username && password) {
-      connect(true);
-    }
+    const restore = async () => {
+      try {
+        const restored = await invoke<{
+          server_url: string;
+          username: string;
+          master_key: string;
+          key_status: "available" | "locked" | "absent";
+        } | null>("restore_session");
+        if (restored) {
+          setServerUrl(restored.server_url);
+          setUsername(restored.username);
+          setConnected(true);
+          addLog("info", "Session restored from keyring");
+          if (restored.key_status === "locked") {
+            await promptUnlock();
+          }
+          await loadRemote(null);
+          return;
+        }
+      } catch (err) {
+        addLog("warn", `Session restore failed: ${String(err)}`);
+      }
+      if (serverUrl && username && password) {
+        connect(true);
+      }
+    };
+    restore();
   }, [autoLoginTried, serverUrl, username, password]);
 
   const loadRemote = async (folderId: string | null) => {
@@ -529,6 +593,10 @@ export default function App() {
       addLog("info", `Loaded folders=${folderData.length} archives=${archiveData.length}`);
     } catch (err) {
       console.error(err);
+      if (String(err).includes("session_expired")) {
+        setConnected(false);
+        setLoginError("Session expired. Please sign in again.");
+      }
       setLoadError("Failed to load remote data. Check server and credentials.");
       addLog("error", `Load failed: ${String(err)}`);
       throw err;
@@ -550,12 +618,15 @@ export default function App() {
     }
     try {
       addLog("info", `Download queued: ${name}`);
-      const id = await invoke<string>("start_archive_download", {
+      const started = await invoke<{ id: string; path: string; skipped: boolean }>("start_archive_download", {
         archiveId,
         downloadDir: downloadPath,
         fileIndex
       });
-      const targetPath = `${downloadPath.replace(/[\\/]+$/, "")}\\${name}`;
+      const id = started.id;
+      if (started.skipped) {
+        addLog("info", `Download skipped, file exists: ${started.path}`);
+      }
       setDownloads((prev) => ({
         ...prev,
         [id]: {
@@ -564,8 +635,8 @@ export default function App() {
           downloaded: 0,
           total: 0,
           speed: 0,
-          status: "queued",
-          path: targetPath
+          status: started.skipped ? "completed" : "queued",
+          path: started.path
         }
       }));
     } catch (err) {
@@ -610,29 +681,48 @@ export default function App() {
 
 
   const enqueueDownload = (archiveId: string, name: string, fileIndex?: number) => {
-    const active = Object.values(downloads).filter((d) => !["completed", "error", "paused"].includes(d.status)).length;
-    if (active >= maxConcurrent) {
-      setQueue((prev) => [...prev, { archiveId, name, fileIndex }]);
-    } else {
-      startDownloadNow(archiveId, name, fileIndex);
-    }
+    startDownloadNow(archiveId, name, fileIndex);
   };
 
-  useEffect(() => {
-    const active = Object.values(downloads).filter((d) => !["completed", "error", "paused"].includes(d.status)).length;
-    if (queue.length === 0) return;
-    if (active >= maxConcurrent) return;
-    const next = queue[0];
-    setQueue((prev) => prev.slice(1));
-    startDownloadNow(next.archiveId, next.name, next.fileIndex);
-  }, [downloads, queue, maxConcurrent]);
-
   useEffect(() => {
     localStorage.setItem("maxConcurrent", String(maxConcurrent));
+    invoke("set_max_concurrent", { n: maxConcurrent }).catch((err) => {
+      addLog("error", `Set parallel downloads failed: ${String(err)}`);
+    });
   }, [maxConcurrent]);
 
-  const pauseDownload = async (id: string) => {
-    await invoke("pause_download", { id });
+  const pauseAll = async () => {
+    await invoke<string[]>("pause_all");
+  };
+
+  const resumeAll = async () => {
+    try {
+      await invoke<string[]>("resume_all");
+    } catch (err) {
+      addLog("error", `Resume all failed: ${String(err)}`);
+    }
+  };
+
+  const cancelAll = async () => {
+    try {
+      const count = await invoke<number>("cancel_all");
+      addLog("info", `Cancelled ${count} downloads`);
+    } catch (err) {
+      addLog("error", `Cancel all failed: ${String(err)}`);
+    }
+  };
+
+  const removeDownload = async (id: string) => {
+    try {
+      await invoke("remove_download", { id });
+      setDownloads((prev) => {
+        const next = { ...prev };
+        delete next[id];
+        return next;
+      });
+    } catch (err) {
+      addLog("error", `Dismiss failed: ${String(err)}`);
+    }
   };
 
   if (!connected) {
@@ -712,7 +802,9 @@ export default function App() {
 
         <div className="download-actions">
           <button className="primary" disabled={!connected} onClick={() => loadRemote(currentFolderId)}>Refresh</button>
-          <button onClick={() => Object.values(downloads).forEach((d) => pauseDownload(d.id))}>Pause All</button>
+          <button onClick={pauseAll}>Pause All</button>
+          <button onClick={resumeAll}>Resume All</button>
+          <button onClick={cancelAll}>Cancel All</button>
           <button disabled={selectedDownloads.length === 0} onClick={requestDeleteSelection}>Delete Selected</button>
           <button onClick={pickDownloadDir}>Set folder</button>
           <div className="concurrency">
@@ -738,7 +830,7 @@ export default function App() {
         >
           {filteredDownloads.map((item) => {
             const pct = item.total ? Math.min(100, Math.floor((item.downloaded / item.total) * 100)) : 0;
-            const eta = item.total && item.speed ? formatDuration((item.total - item.downloaded) / item.speed) : "";
+            const eta = item.eta_seconds != null ? formatDuration(item.eta_seconds) : "";
             const isSelected = selectedDownloads.includes(item.id);
             return (
               <div
@@ -767,6 +859,16 @@ export default function App() {
                     <p className="file-name">{item.name}</p>
                     <p className="file-meta">{formatSize(item.total)} · {item.status}</p>
                   </div>
+                  {(item.status === "completed" || item.status === "error") && (
+                    <button
+                      onClick={(e) => {
+                        e.stopPropagation();
+                        removeDownload(item.id);
+                      }}
+                    >
+                      Dismiss
+                    </button>
+                  )}
                 </div>
                 <div className="progress-row">
                   <div className="progress-bar">